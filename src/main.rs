@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::Cursor;
 
 use anyhow::Result;
@@ -5,7 +6,7 @@ use macroquad::{
     experimental::animation::{AnimatedSprite, Animation},
     prelude::*,
 };
-use tiled::{DefaultResourceCache, Loader, Map, Tileset};
+use tiled::{DefaultResourceCache, Frame, Loader, Map, Tileset};
 
 /// Custom Reader so that we can read tiles in wasm.
 struct TiledReader;
@@ -30,42 +31,439 @@ impl tiled::ResourceReader for TiledReader {
     }
 }
 
-fn draw_background(world_map: &Map, tileset: &Tileset, texture: &Texture2D) {
-    for layer in world_map.layers() {
-        let layer = layer.as_tile_layer().unwrap();
-        for x in 0..layer.width().unwrap() {
-            for y in 0..layer.height().unwrap() {
-                if let Some(tile) = layer.get_tile(x as i32, y as i32) {
-                    let tile_id = tile.id();
-                    let tile_width = tileset.tile_width;
-                    let tile_height = tileset.tile_height;
-                    let spacing = tileset.spacing;
-                    let margin = tileset.margin;
-                    let tiles_per_row =
-                        (texture.size().x as u32 - margin + spacing) / (tile_width + spacing);
-                    let tileset_texture_x = tile_id % tiles_per_row * tile_width;
-                    let tileset_texture_y = tile_id / tiles_per_row * tile_height;
-
-                    draw_texture_ex(
-                        &texture,
-                        (x * tile_width) as f32,
-                        (y * tile_height) as f32,
-                        WHITE,
-                        DrawTextureParams {
-                            flip_x: tile.flip_h,
-                            flip_y: tile.flip_v,
-                            source: Some(Rect::new(
-                                tileset_texture_x as f32,
-                                tileset_texture_y as f32,
-                                tile_width as f32,
-                                tile_height as f32,
-                            )),
-                            ..Default::default()
-                        },
-                    )
+/// Precomputed per-tileset animation timing, built once at load time so the per-frame draw
+/// loop only has to do a cheap lookup instead of walking the tiled animation frames.
+struct TileAnimations {
+    /// Maps an animated tile's id to its frame sequence and total cycle length, in seconds.
+    by_tile_id: HashMap<u32, (Vec<Frame>, f64)>,
+    /// The least common multiple of all animations' cycle lengths, used to keep the caller's
+    /// elapsed-time accumulator from growing without bound over a long session without
+    /// putting any animation out of step when it wraps.
+    common_cycle_duration: f64,
+}
+
+impl TileAnimations {
+    fn new(tileset: &Tileset) -> Self {
+        let mut by_tile_id = HashMap::new();
+        let mut common_cycle_ms: u64 = 0;
+
+        for (tile_id, tile) in tileset.tiles() {
+            if let Some(frames) = &tile.animation {
+                let total_duration_ms: u32 = frames.iter().map(|frame| frame.duration).sum();
+                if total_duration_ms > 0 {
+                    let total_duration = total_duration_ms as f64 / 1000.0;
+                    common_cycle_ms = if common_cycle_ms == 0 {
+                        total_duration_ms as u64
+                    } else {
+                        let (mut a, mut b) = (common_cycle_ms, total_duration_ms as u64);
+                        while b != 0 {
+                            (a, b) = (b, a % b);
+                        }
+                        (common_cycle_ms / a).saturating_mul(total_duration_ms as u64)
+                    };
+                    by_tile_id.insert(tile_id, (frames.clone(), total_duration));
                 }
             }
         }
+
+        TileAnimations {
+            by_tile_id,
+            common_cycle_duration: common_cycle_ms as f64 / 1000.0,
+        }
+    }
+
+    /// How often the caller's elapsed-time accumulator should be wrapped via `elapsed %
+    /// cycle_duration()` to keep it bounded. Falls back to a sensible default if the tileset
+    /// has no animated tiles at all.
+    fn cycle_duration(&self) -> f64 {
+        if self.common_cycle_duration > 0.0 {
+            self.common_cycle_duration
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the tile id that should currently be displayed in place of `tile_id`, given
+    /// how much time has elapsed since the background started animating.
+    fn current_tile_id(&self, tile_id: u32, elapsed: f64) -> u32 {
+        let Some((frames, total_duration)) = self.by_tile_id.get(&tile_id) else {
+            return tile_id;
+        };
+
+        let mut t = elapsed % total_duration;
+        for frame in frames {
+            let frame_duration = frame.duration as f64 / 1000.0;
+            if t < frame_duration {
+                return frame.tile_id;
+            }
+            t -= frame_duration;
+        }
+
+        frames.last().map(|frame| frame.tile_id).unwrap_or(tile_id)
+    }
+}
+
+/// The name Tiled layer that, along with every layer after it, is drawn over characters
+/// instead of under them (tree tops, roofs, and other foreground decoration).
+const FOREGROUND_LAYER_NAME: &str = "Foreground";
+
+/// A tile id, local to a tileset, that is always fully transparent, following the gsa
+/// console's convention of reserving a sentinel index for "no tile here". This is distinct
+/// from a cell simply having no tile reference at all (gid 0 in Tiled), which `get_tile`
+/// already reports as `None` and is handled separately. Local tile id 0 is otherwise a
+/// perfectly normal, renderable tile (the top-left tile of the tileset image), so the
+/// sentinel defaults to `None` ("no sentinel") and must be opted into explicitly.
+const EMPTY_TILE_ID: Option<u32> = None;
+
+/// Draws a single tile from `tileset`'s source texture at tile coordinates `(x, y)`, tinted
+/// by `tint` (used to apply a layer's opacity). Tiles equal to `EMPTY_TILE_ID` are skipped.
+fn draw_tile(
+    texture: &Texture2D,
+    tileset: &Tileset,
+    tile_id: u32,
+    flip_h: bool,
+    flip_v: bool,
+    x: u32,
+    y: u32,
+    tint: Color,
+) {
+    if Some(tile_id) == EMPTY_TILE_ID {
+        return;
+    }
+
+    let tile_width = tileset.tile_width;
+    let tile_height = tileset.tile_height;
+    let spacing = tileset.spacing;
+    let margin = tileset.margin;
+    let tiles_per_row = (texture.size().x as u32 - margin + spacing) / (tile_width + spacing);
+    let tileset_texture_x = tile_id % tiles_per_row * tile_width;
+    let tileset_texture_y = tile_id / tiles_per_row * tile_height;
+
+    draw_texture_ex(
+        texture,
+        (x * tile_width) as f32,
+        (y * tile_height) as f32,
+        tint,
+        DrawTextureParams {
+            flip_x: flip_h,
+            flip_y: flip_v,
+            source: Some(Rect::new(
+                tileset_texture_x as f32,
+                tileset_texture_y as f32,
+                tile_width as f32,
+                tile_height as f32,
+            )),
+            ..Default::default()
+        },
+    )
+}
+
+/// One tile layer, baked once into a cached texture so the per-frame draw loop doesn't have
+/// to issue a `draw_texture_ex` call per tile. Only the tiles that carry an animation are
+/// tracked separately and redrawn live over the baked texture every frame.
+struct BakedLayer {
+    texture: RenderTarget,
+    /// `(x, y, tile_id, flip_h, flip_v)` for every animated tile in this layer, in tile
+    /// coordinates, so they can be redrawn live each frame over the static bake.
+    animated_tiles: Vec<(u32, u32, u32, bool, bool)>,
+    /// The layer's opacity, from Tiled, applied when compositing it over lower layers.
+    opacity: f32,
+}
+
+/// The world's tile layers, pre-rendered into cached textures at load time (the tile-cache
+/// idea: look up a prepared surface by tile id rather than recomputing it every frame).
+/// Layers are composited back-to-front with per-layer opacity, and the layers from
+/// [`FOREGROUND_LAYER_NAME`] onward are drawn separately so they can be interleaved with
+/// character draws, letting sprites pass behind foreground decoration.
+pub struct Tilemap {
+    layers: Vec<BakedLayer>,
+    /// Index of the first layer that should be drawn over characters rather than under them.
+    foreground_start: Option<usize>,
+}
+
+impl Tilemap {
+    /// Bakes every tile layer of `world_map` into its own cached texture, leaving animated
+    /// tiles out so they can be redrawn live by `draw_below_characters`/`draw_above_characters`.
+    fn new(
+        world_map: &Map,
+        tileset: &Tileset,
+        texture: &Texture2D,
+        tile_animations: &TileAnimations,
+    ) -> Self {
+        let map_width = world_map.width * world_map.tile_width;
+        let map_height = world_map.height * world_map.tile_height;
+
+        let mut layers = Vec::new();
+        let mut foreground_start = None;
+
+        for layer in world_map.layers() {
+            let Some(tile_layer) = layer.as_tile_layer() else {
+                continue;
+            };
+
+            if layer.name == FOREGROUND_LAYER_NAME {
+                foreground_start = Some(layers.len());
+            }
+
+            let bake_target = render_target(map_width, map_height);
+            bake_target.texture.set_filter(FilterMode::Nearest);
+
+            let mut bake_camera = Camera2D::from_display_rect(Rect::new(
+                0.0,
+                0.0,
+                map_width as f32,
+                map_height as f32,
+            ));
+            bake_camera.render_target = Some(bake_target.clone());
+            set_camera(&bake_camera);
+            clear_background(BLANK);
+
+            let mut animated_tiles = Vec::new();
+
+            for x in 0..tile_layer.width().unwrap() {
+                for y in 0..tile_layer.height().unwrap() {
+                    if let Some(tile) = tile_layer.get_tile(x as i32, y as i32) {
+                        if Some(tile.id()) == EMPTY_TILE_ID {
+                            continue;
+                        }
+                        if tile_animations.by_tile_id.contains_key(&tile.id()) {
+                            animated_tiles.push((x, y, tile.id(), tile.flip_h, tile.flip_v));
+                            continue;
+                        }
+                        draw_tile(
+                            texture,
+                            tileset,
+                            tile.id(),
+                            tile.flip_h,
+                            tile.flip_v,
+                            x,
+                            y,
+                            WHITE,
+                        );
+                    }
+                }
+            }
+
+            set_default_camera();
+
+            layers.push(BakedLayer {
+                texture: bake_target,
+                animated_tiles,
+                opacity: layer.opacity,
+            });
+        }
+
+        Tilemap {
+            layers,
+            foreground_start,
+        }
+    }
+
+    /// Draws every baked layer (and its live animated tiles) in `range`, back-to-front.
+    fn draw_layers(
+        &self,
+        range: std::ops::Range<usize>,
+        tileset: &Tileset,
+        texture: &Texture2D,
+        tile_animations: &TileAnimations,
+        elapsed: f64,
+    ) {
+        for layer in &self.layers[range] {
+            let tint = Color::new(1.0, 1.0, 1.0, layer.opacity);
+
+            draw_texture_ex(
+                &layer.texture.texture,
+                0.0,
+                0.0,
+                tint,
+                DrawTextureParams {
+                    flip_y: true, // Must flip y otherwise the bake render target will be upside down
+                    ..Default::default()
+                },
+            );
+
+            for &(x, y, tile_id, flip_h, flip_v) in &layer.animated_tiles {
+                let resolved_id = tile_animations.current_tile_id(tile_id, elapsed);
+                draw_tile(texture, tileset, resolved_id, flip_h, flip_v, x, y, tint);
+            }
+        }
+    }
+
+    /// Draws every layer below [`FOREGROUND_LAYER_NAME`] (or all layers, if the map doesn't
+    /// define one). Call this before drawing characters.
+    pub fn draw_below_characters(
+        &self,
+        tileset: &Tileset,
+        texture: &Texture2D,
+        tile_animations: &TileAnimations,
+        elapsed: f64,
+    ) {
+        let end = self.foreground_start.unwrap_or(self.layers.len());
+        self.draw_layers(0..end, tileset, texture, tile_animations, elapsed);
+    }
+
+    /// Draws [`FOREGROUND_LAYER_NAME`] and every layer after it. Call this after drawing
+    /// characters so foreground decoration can occlude them.
+    pub fn draw_above_characters(
+        &self,
+        tileset: &Tileset,
+        texture: &Texture2D,
+        tile_animations: &TileAnimations,
+        elapsed: f64,
+    ) {
+        let start = self.foreground_start.unwrap_or(self.layers.len());
+        self.draw_layers(
+            start..self.layers.len(),
+            tileset,
+            texture,
+            tile_animations,
+            elapsed,
+        );
+    }
+}
+
+/// A spawn point defined by a named object on the map's "Spawns" object layer, giving a
+/// character's starting position and job instead of hard-coding it in `main`.
+pub struct Spawn {
+    pub name: String,
+    pub job: String,
+    pub position: Vec2,
+}
+
+/// Collision rectangles and named trigger zones parsed from the map's Tiled object layers,
+/// making `world.tmx` the single source of truth for level layout.
+pub struct WorldLayout {
+    /// Rectangles, in pixel space, that characters cannot walk through.
+    collision: Vec<Rect>,
+    /// Named trigger zones, e.g. for doors or scripted events.
+    triggers: Vec<(String, Rect)>,
+}
+
+impl WorldLayout {
+    /// Parses the map's object layers into a `WorldLayout` and the list of character spawns.
+    /// A "Collision" layer supplies blocking rectangles, a "Triggers" layer supplies named
+    /// trigger zones, and a "Spawns" layer supplies character spawn points.
+    fn from_map(world_map: &Map) -> (Self, Vec<Spawn>) {
+        let mut collision = Vec::new();
+        let mut triggers = Vec::new();
+        let mut spawns = Vec::new();
+
+        for layer in world_map.layers() {
+            let Some(object_layer) = layer.as_object_layer() else {
+                continue;
+            };
+
+            for object in object_layer.objects() {
+                let rect = Rect::new(object.x, object.y, object.width, object.height);
+
+                match layer.name.as_str() {
+                    "Collision" => collision.push(rect),
+                    "Triggers" => triggers.push((object.name.clone(), rect)),
+                    "Spawns" => spawns.push(Spawn {
+                        name: object.name.clone(),
+                        job: object
+                            .properties
+                            .get("job")
+                            .and_then(|value| match value {
+                                tiled::PropertyValue::StringValue(job) => Some(job.clone()),
+                                _ => None,
+                            })
+                            .unwrap_or_default(),
+                        position: vec2(object.x, object.y),
+                    }),
+                    _ => {}
+                }
+            }
+        }
+
+        (
+            WorldLayout {
+                collision,
+                triggers,
+            },
+            spawns,
+        )
+    }
+
+    /// Returns whether `rect` overlaps any collision rectangle.
+    fn blocks(&self, rect: Rect) -> bool {
+        self.collision.iter().any(|blocker| blocker.overlaps(&rect))
+    }
+
+    /// Returns the name of the trigger zone `position` currently falls inside, if any.
+    fn trigger_at(&self, position: Vec2) -> Option<&str> {
+        self.triggers
+            .iter()
+            .find(|(_, rect)| rect.contains(position))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// Which way a `Character` is currently facing, used to pick the right row out of the
+/// idle/walk animation sprite sheets.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Facing {
+    Down,
+    Left,
+    Up,
+    Right,
+}
+
+/// The speed, in pixels per second, that characters walk at.
+const WALK_SPEED: f32 = 60.0;
+
+/// The size, in pixels, of a character's collision box, matching the sprite tile size.
+const CHARACTER_SIZE: Vec2 = vec2(16.0, 16.0);
+
+/// Reads the currently pressed direction keys and returns a normalized movement vector.
+fn input_dir() -> Vec2 {
+    let mut dir = vec2(0.0, 0.0);
+
+    if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
+        dir.x -= 1.0;
+    }
+    if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
+        dir.x += 1.0;
+    }
+    if is_key_down(KeyCode::Up) || is_key_down(KeyCode::W) {
+        dir.y -= 1.0;
+    }
+    if is_key_down(KeyCode::Down) || is_key_down(KeyCode::S) {
+        dir.y += 1.0;
+    }
+
+    dir.normalize_or_zero()
+}
+
+/// Affine transform applied when the background render target is blitted to the screen,
+/// independent of the aspect-preserving fit. `origin` is the pivot in map space that stays
+/// centered on screen, letting the whole tiled world be rotated and zoomed about an
+/// arbitrary point, e.g. for Mode-7-style camera spins.
+pub struct Background {
+    pub rotation: f32,
+    pub scale: Vec2,
+    pub origin: Vec2,
+}
+
+impl Background {
+    fn new(map_width: f32, map_height: f32) -> Self {
+        Background {
+            rotation: 0.0,
+            scale: vec2(1.0, 1.0),
+            origin: vec2(map_width / 2.0, map_height / 2.0),
+        }
+    }
+}
+
+/// Looks up the idle/walk sprite sheet paths for a spawned character by name. The object
+/// layer is the source of truth for spawn position and job, but the map format has no slot
+/// for art assets, so sprite sheets are still matched up by name.
+fn character_sprites(name: &str) -> Option<(&'static str, &'static str)> {
+    match name {
+        "Kas" => Some(("data/char1_idle.png", "data/char1_walk.png")),
+        "Jeid" => Some(("data/char2_idle.png", "data/char2_walk.png")),
+        "Bres" => Some(("data/char3_idle.png", "data/char3_walk.png")),
+        _ => None,
     }
 }
 
@@ -73,6 +471,11 @@ pub struct Character {
     pub name: String,
     pub job: String,
     pub position: Vec2,
+    pub facing: Facing,
+    pub is_moving: bool,
+    /// Whether this character reads keyboard input in `update`. Only one character should be
+    /// the player; the rest stay stationary (or, eventually, are driven by AI).
+    pub is_player: bool,
     pub idle_sprite: AnimatedSprite,
     pub idle_texture: Texture2D,
     pub walk_sprite: AnimatedSprite,
@@ -84,6 +487,7 @@ impl Character {
         name: &str,
         job: &str,
         position: Vec2,
+        is_player: bool,
         idle_animation: &str,
         walk_animation: &str,
     ) -> Result<Character> {
@@ -157,6 +561,9 @@ impl Character {
             name: name.to_string(),
             job: job.to_string(),
             position,
+            facing: Facing::Down,
+            is_moving: false,
+            is_player,
             idle_sprite,
             idle_texture,
             walk_sprite,
@@ -164,20 +571,90 @@ impl Character {
         })
     }
 
+    /// Reads directional input (for the player only) and moves the character, switching facing and
+    /// idle/walk animation as appropriate. `dt` is the frame time in seconds. Movement is
+    /// tested against `world_layout`'s collision rectangles one axis at a time, so a
+    /// character sliding into a wall diagonally still slides along it.
+    pub fn update(&mut self, dt: f32, world_layout: &WorldLayout) {
+        let dir = if self.is_player {
+            input_dir()
+        } else {
+            Vec2::ZERO
+        };
+        self.is_moving = dir != Vec2::ZERO;
+
+        if self.is_moving {
+            self.facing = if dir.x.abs() > dir.y.abs() {
+                if dir.x < 0.0 {
+                    Facing::Left
+                } else {
+                    Facing::Right
+                }
+            } else if dir.y < 0.0 {
+                Facing::Up
+            } else {
+                Facing::Down
+            };
+
+            let delta = dir * WALK_SPEED * dt;
+
+            let moved_x = vec2(self.position.x + delta.x, self.position.y);
+            if !world_layout.blocks(Rect::new(
+                moved_x.x,
+                moved_x.y,
+                CHARACTER_SIZE.x,
+                CHARACTER_SIZE.y,
+            )) {
+                self.position.x = moved_x.x;
+            }
+
+            let moved_y = vec2(self.position.x, self.position.y + delta.y);
+            if !world_layout.blocks(Rect::new(
+                moved_y.x,
+                moved_y.y,
+                CHARACTER_SIZE.x,
+                CHARACTER_SIZE.y,
+            )) {
+                self.position.y = moved_y.y;
+            }
+        }
+
+        // Both sprite sheets were built with the same down/left/up/right row order, so the
+        // facing maps directly onto the animation index regardless of idle vs walk.
+        let animation = match self.facing {
+            Facing::Down => 0,
+            Facing::Left => 1,
+            Facing::Up => 2,
+            Facing::Right => 3,
+        };
+
+        if self.is_moving {
+            self.walk_sprite.set_animation(animation);
+        } else {
+            self.idle_sprite.set_animation(animation);
+        }
+    }
+
     pub fn draw(&mut self) {
+        let (texture, sprite) = if self.is_moving {
+            (&self.walk_texture, &mut self.walk_sprite)
+        } else {
+            (&self.idle_texture, &mut self.idle_sprite)
+        };
+
         draw_texture_ex(
-            &self.idle_texture,
+            texture,
             self.position.x,
             self.position.y,
             WHITE,
             DrawTextureParams {
-                dest_size: Some(self.idle_sprite.frame().dest_size),
-                source: Some(self.idle_sprite.frame().source_rect),
+                dest_size: Some(sprite.frame().dest_size),
+                source: Some(sprite.frame().source_rect),
                 ..Default::default()
             },
         );
 
-        self.idle_sprite.update();
+        sprite.update();
     }
 }
 
@@ -193,6 +670,15 @@ async fn main() -> Result<()> {
     let background_texture = load_texture("data/MasterSimple.png").await.unwrap();
     background_texture.set_filter(FilterMode::Nearest);
 
+    let tile_animations = TileAnimations::new(&background_tileset);
+    let tilemap = Tilemap::new(
+        &world_map,
+        &background_tileset,
+        &background_texture,
+        &tile_animations,
+    );
+    let mut background_elapsed: f64 = 0.0;
+
     // We want to be able to resize the window in such a way that the contents are always
     // aspect-preserved while always getting scaled in the best possible way.
     let map_width = (world_map.width * world_map.tile_width) as f32;
@@ -204,58 +690,94 @@ async fn main() -> Result<()> {
         Camera2D::from_display_rect(Rect::new(0.0, 0.0, map_width, map_height));
     render_target_camera.render_target = Some(render_target.clone());
 
-    let mut characters = vec![
-        Character::new(
-            "Kas",
-            "Shopkeeper",
-            vec2(100.0, 100.0),
-            "data/char1_idle.png",
-            "data/char1_walk.png",
-        )
-        .await?,
-        Character::new(
-            "Jeid",
-            "Barkeeper",
-            vec2(200.0, 200.0),
-            "data/char2_idle.png",
-            "data/char2_walk.png",
-        )
-        .await?,
-        Character::new(
-            "Bres",
-            "Peasant",
-            vec2(400.0, 230.0),
-            "data/char3_idle.png",
-            "data/char3_walk.png",
-        )
-        .await?,
-    ];
+    let background = Background::new(map_width, map_height);
+
+    let (world_layout, spawns) = WorldLayout::from_map(&world_map);
+
+    let mut characters = Vec::new();
+    for spawn in &spawns {
+        let Some((idle_sprite, walk_sprite)) = character_sprites(&spawn.name) else {
+            continue;
+        };
+        // Only the first spawned character is player-controlled; the rest stay put. The
+        // trigger HUD below also treats `characters.first()` as the player.
+        let is_player = characters.is_empty();
+        characters.push(
+            Character::new(
+                &spawn.name,
+                &spawn.job,
+                spawn.position,
+                is_player,
+                idle_sprite,
+                walk_sprite,
+            )
+            .await?,
+        );
+    }
 
     loop {
+        let dt = get_frame_time();
+        // Wrap into the animation cycle length each frame instead of letting this grow
+        // forever, so precision stays stable across long play sessions.
+        background_elapsed = (background_elapsed + dt as f64) % tile_animations.cycle_duration();
+
         set_camera(&render_target_camera);
 
-        draw_background(&world_map, &background_tileset, &background_texture);
+        tilemap.draw_below_characters(
+            &background_tileset,
+            &background_texture,
+            &tile_animations,
+            background_elapsed,
+        );
 
-        // Draw characters.
+        // Update and draw characters.
         for character in &mut characters {
+            character.update(dt, &world_layout);
             character.draw();
         }
 
+        // Foreground decoration (tree tops, roofs) draws on top so sprites can pass behind it.
+        tilemap.draw_above_characters(
+            &background_tileset,
+            &background_texture,
+            &tile_animations,
+            background_elapsed,
+        );
+
         // Draw FPS.
         draw_text(format!("FPS: {}", get_fps()).as_str(), 8., 16., 16., WHITE);
 
+        if let Some(trigger) = characters
+            .first()
+            .and_then(|character| world_layout.trigger_at(character.position))
+        {
+            draw_text(&format!("Trigger: {trigger}"), 8., 32., 16., WHITE);
+        }
+
         set_default_camera();
         clear_background(BLACK);
 
         let zoom = f32::min(screen_width() / map_width, screen_height() / map_height);
+        let effective_scale = vec2(zoom, zoom) * background.scale;
+
+        // Clamp the origin to the map bounds so the pivot kept at the screen center always
+        // lies on the map, even while panned or rotated.
+        let origin = background
+            .origin
+            .clamp(Vec2::ZERO, vec2(map_width, map_height));
+        let screen_center = vec2(screen_width(), screen_height()) * 0.5;
+        let position = screen_center - origin * effective_scale;
+
         draw_texture_ex(
             &render_target.texture,
-            (screen_width() - (map_width * zoom)) * 0.5,
-            (screen_height() - (map_height * zoom)) * 0.5,
+            position.x,
+            position.y,
             WHITE,
             DrawTextureParams {
-                dest_size: Some(vec2(map_width * zoom, map_height * zoom)),
+                dest_size: Some(vec2(map_width, map_height) * effective_scale),
                 flip_y: true, // Must flip y otherwise 'render_target' will be upside down
+                rotation: background.rotation,
+                pivot: Some(screen_center),
                 ..Default::default()
             },
         );